@@ -0,0 +1,54 @@
+/// Escapes all regular expression metacharacters in `text`.
+///
+/// The string returned is guaranteed to match the literal text of `text`
+/// when used as an Oniguruma pattern, which makes it safe to splice
+/// caller-provided text into a larger pattern.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate onig; use onig::escape;
+/// # fn main() {
+/// assert_eq!(escape("1.5-2.0?"), "1\\.5\\-2\\.0\\?");
+/// # }
+/// ```
+pub fn escape(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len());
+    for c in text.chars() {
+        if is_meta_character(c) {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted
+}
+
+/// Returns true if the character has significance in a regular expression
+/// and therefore needs to be escaped.
+fn is_meta_character(c: char) -> bool {
+    match c {
+        '.' | '^' | '$' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' |
+        '|' | '\\' | '-' | '#' | ' ' | '\t' | '\n' | '\r' | '\x0b' | '\x0c' => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape;
+
+    #[test]
+    fn test_escape_metacharacters() {
+        assert_eq!(escape("1.5-2.0?"), "1\\.5\\-2\\.0\\?");
+    }
+
+    #[test]
+    fn test_escape_no_metacharacters() {
+        assert_eq!(escape("abc123"), "abc123");
+    }
+
+    #[test]
+    fn test_escape_whitespace() {
+        assert_eq!(escape("a b"), "a\\ b");
+    }
+}
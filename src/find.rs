@@ -1,22 +1,26 @@
 use std::iter::Iterator;
+use std::ops::Range;
 use super::{Region, Regex, SEARCH_OPTION_NONE};
 
 impl Regex {
     /// Returns the capture groups corresponding to the leftmost-first match
     /// in text. Capture group `0` always corresponds to the entire match.
     /// If no match is found, then `None` is returned.
-    pub fn captures<'t>(&self, text: &'t str) -> Option<Captures<'t>> {
+    pub fn captures<'r, 't>(&'r self, text: &'t str) -> Option<Captures<'r, 't>> {
         let mut region = Region::new();
         self.search_with_options(text, 0, text.len(),
                                  SEARCH_OPTION_NONE, Some(&mut region))
             .map(|_| Captures {
+                regex: self,
                 text: text,
                 region: region,
             })
     }
 
     /// Returns an iterator for each successive non-overlapping match in `text`,
-    /// returning the start and end byte indices with respect to `text`.
+    /// returning a `Match` for each one. Its `start`, `end` and `range`
+    /// methods give the byte indices of the match with respect to `text`;
+    /// a `Match` does not compare equal to a `(usize, usize)` tuple.
     ///
     /// # Example
     ///
@@ -143,14 +147,16 @@ impl Regex {
 /// index corresponds to the next capture group in the regex. Positions
 /// returned from a capture group are always byte indices.
 ///
-/// `'t` is the lifetime of the matched text.
+/// `'r` is the lifetime of the `Regex` struct and `'t` is the lifetime of
+/// the matched text.
 #[derive(Debug)]
-pub struct Captures<'t> {
+pub struct Captures<'r, 't> {
+    regex: &'r Regex,
     text: &'t str,
     region: Region,
 }
 
-impl<'t> Captures<'t> {
+impl<'r, 't> Captures<'r, 't> {
     /// Returns the start and end positions of the Nth capture group. Returns
     /// `None` if i is not a valid capture group or if the capture group did
     /// not match anything. The positions returned are always byte indices with
@@ -165,6 +171,59 @@ impl<'t> Captures<'t> {
         self.pos(pos).map(|(beg, end)| &self.text[beg..end])
     }
 
+    /// Returns the start and end positions of the capture group named
+    /// `name`. Returns `None` if `name` isn't a valid capture group name
+    /// for this pattern, or if none of the groups it refers to matched.
+    /// A name can be shared by several numbered groups (e.g. inside
+    /// alternation); the position of the last one that matched is
+    /// returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate onig; use onig::Regex;
+    /// # fn main() {
+    /// let re = Regex::new(r"(?<year>\d{4})-(?<month>\d{2})").unwrap();
+    /// let caps = re.captures("2014-05").unwrap();
+    /// assert_eq!(caps.name_pos("year"), Some((0, 4)));
+    /// # }
+    /// ```
+    pub fn name_pos(&self, name: &str) -> Option<(usize, usize)> {
+        self.regex.capture_group_numbers_for_name(name)
+            .iter()
+            .filter_map(|&group| self.pos(group as usize))
+            .last()
+    }
+
+    /// Returns the matched string for the capture group named `name`. If
+    /// `name` isn't a valid capture group name for this pattern, or none
+    /// of the groups it refers to matched, then `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate onig; use onig::Regex;
+    /// # fn main() {
+    /// let re = Regex::new(r"(?<year>\d{4})-(?<month>\d{2})").unwrap();
+    /// let caps = re.captures("2014-05").unwrap();
+    /// assert_eq!(caps.name("year"), Some("2014"));
+    /// # }
+    /// ```
+    pub fn name(&self, name: &str) -> Option<&'t str> {
+        self.name_pos(name).map(|(beg, end)| &self.text[beg..end])
+    }
+
+    /// Returns the `Match` for the capture group `i`, superseding separate
+    /// calls to `pos`/`at`. Returns `None` if `i` is not a valid capture
+    /// group or if the capture group did not match anything.
+    pub fn get(&self, pos: usize) -> Option<Match<'t>> {
+        self.pos(pos).map(|(beg, end)| Match {
+            text: self.text,
+            start: beg,
+            end: end,
+        })
+    }
+
     /// Returns the number of captured groups.
     pub fn len(&self) -> usize {
         self.region.len()
@@ -177,7 +236,7 @@ impl<'t> Captures<'t> {
 
     /// Creates an iterator of all the capture groups in order of appearance in
     /// the regular expression.
-    pub fn iter(&'t self) -> SubCaptures<'t> {
+    pub fn iter(&'t self) -> SubCaptures<'r, 't> {
         SubCaptures {
             idx: 0,
             caps: self,
@@ -187,7 +246,7 @@ impl<'t> Captures<'t> {
     /// Creates an iterator of all the capture group positions in order of
     /// appearance in the regular expression. Positions are byte indices in
     /// terms of the original string matched.
-    pub fn iter_pos(&'t self) -> SubCapturesPos<'t> {
+    pub fn iter_pos(&'t self) -> SubCapturesPos<'r, 't> {
         SubCapturesPos {
             idx: 0,
             caps: self,
@@ -198,13 +257,14 @@ impl<'t> Captures<'t> {
 /// An iterator over capture groups for a particular match of a regular
 /// expression.
 ///
-///`'t` is the lifetime of the matched text.
-pub struct SubCaptures<'t> {
+///`'r` is the lifetime of the `Regex` struct and `'t` is the lifetime of
+/// the matched text.
+pub struct SubCaptures<'r, 't> {
     idx: usize,
-    caps: &'t Captures<'t>,
+    caps: &'t Captures<'r, 't>,
 }
 
-impl<'t> Iterator for SubCaptures<'t> {
+impl<'r, 't> Iterator for SubCaptures<'r, 't> {
     type Item = Option<&'t str>;
 
     fn next(&mut self) -> Option<Option<&'t str>> {
@@ -220,14 +280,15 @@ impl<'t> Iterator for SubCaptures<'t> {
 /// An iterator over capture group positions for a particular match of
 /// a regular expression.
 ///
-/// Positions are byte indices in terms of the original
-/// string matched. `'t` is the lifetime of the matched text.
-pub struct SubCapturesPos<'t> {
+/// Positions are byte indices in terms of the original string matched.
+/// `'r` is the lifetime of the `Regex` struct and `'t` is the lifetime of
+/// the matched text.
+pub struct SubCapturesPos<'r, 't> {
     idx: usize,
-    caps: &'t Captures<'t>,
+    caps: &'t Captures<'r, 't>,
 }
 
-impl<'t> Iterator for SubCapturesPos<'t> {
+impl<'r, 't> Iterator for SubCapturesPos<'r, 't> {
     type Item = Option<(usize, usize)>;
 
     fn next(&mut self) -> Option<Option<(usize, usize)>> {
@@ -240,11 +301,46 @@ impl<'t> Iterator for SubCapturesPos<'t> {
     }
 }
 
+/// A single match of a regular expression against a haystack.
+///
+/// A `Match` couples a matched byte range with the haystack it was found
+/// in, so callers don't have to re-slice the text themselves.
+///
+/// `'t` is the lifetime of the matched text.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Match<'t> {
+    text: &'t str,
+    start: usize,
+    end: usize,
+}
+
+impl<'t> Match<'t> {
+    /// Returns the starting byte offset of the match in the haystack.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the ending byte offset of the match in the haystack.
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the range over the starting and ending byte offsets of the
+    /// match in the haystack.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Returns the matched text.
+    pub fn as_str(&self) -> &'t str {
+        &self.text[self.start..self.end]
+    }
+}
+
 /// An iterator over all non-overlapping matches for a particular string.
 ///
-/// The iterator yields a tuple of integers corresponding to the start and end
-/// of the match. The indices are byte offsets. The iterator stops when no more
-/// matches can be found.
+/// The iterator yields a `Match` for each one found. The iterator stops
+/// when no more matches can be found.
 ///
 /// `'r` is the lifetime of the `Regex` struct and `'t` is the lifetime
 /// of the matched string.
@@ -257,9 +353,9 @@ pub struct FindMatches<'r, 't> {
 }
 
 impl<'r, 't> Iterator for FindMatches<'r, 't> {
-    type Item = (usize, usize);
+    type Item = Match<'t>;
 
-    fn next(&mut self) -> Option<(usize, usize)> {
+    fn next(&mut self) -> Option<Match<'t>> {
         if self.last_end > self.text.len() {
             return None
         }
@@ -288,7 +384,7 @@ impl<'r, 't> Iterator for FindMatches<'r, 't> {
             self.skip_next_empty = true;
         }
 
-        Some((s, e))
+        Some(Match { text: self.text, start: s, end: e })
     }
 }
 
@@ -307,9 +403,9 @@ pub struct FindCaptures<'r, 't> {
 }
 
 impl<'r, 't> Iterator for FindCaptures<'r, 't> {
-    type Item = Captures<'t>;
+    type Item = Captures<'r, 't>;
 
-    fn next(&mut self) -> Option<Captures<'t>> {
+    fn next(&mut self) -> Option<Captures<'r, 't>> {
         if self.last_end > self.text.len() {
             return None
         }
@@ -339,6 +435,7 @@ impl<'r, 't> Iterator for FindCaptures<'r, 't> {
             self.skip_next_empty = true;
         }
         Some(Captures {
+            regex: self.regex,
             text: self.text,
             region: region
         })
@@ -369,9 +466,9 @@ impl<'r, 't> Iterator for RegexSplits<'r, 't> {
                     Some(s)
                 }
             }
-            Some((s, e)) => {
-                let matched = &text[self.last..s];
-                self.last = e;
+            Some(m) => {
+                let matched = &text[self.last..m.start()];
+                self.last = m.end();
                 Some(matched)
             }
         }
@@ -456,29 +553,29 @@ mod tests {
     #[test]
     fn test_find_iter() {
         let re = Regex::new(r"\d+").unwrap();
-        let ms = re.find_iter("a12b2").collect::<Vec<_>>();
-        assert_eq!(ms, vec![(1, 3), (4, 5)]);
+        let ms = re.find_iter("a12b2").map(|m| m.range()).collect::<Vec<_>>();
+        assert_eq!(ms, vec![1..3, 4..5]);
     }
 
     #[test]
     fn test_find_iter_one_zero_length() {
         let re = Regex::new(r"\d*").unwrap();
-        let ms = re.find_iter("a1b2").collect::<Vec<_>>();
-        assert_eq!(ms, vec![(0, 0), (1, 2), (3, 4)]);
+        let ms = re.find_iter("a1b2").map(|m| m.range()).collect::<Vec<_>>();
+        assert_eq!(ms, vec![0..0, 1..2, 3..4]);
     }
 
     #[test]
     fn test_find_iter_many_zero_length() {
         let re = Regex::new(r"\d*").unwrap();
-        let ms = re.find_iter("a1bbb2").collect::<Vec<_>>();
-        assert_eq!(ms, vec![(0, 0), (1, 2), (3, 3), (4, 4), (5, 6)]);
+        let ms = re.find_iter("a1bbb2").map(|m| m.range()).collect::<Vec<_>>();
+        assert_eq!(ms, vec![0..0, 1..2, 3..3, 4..4, 5..6]);
     }
 
     #[test]
     fn test_zero_length_matches_jumps_past_match_location() {
         let re = Regex::new(r"\b").unwrap();
-        let matches = re.find_iter("test string").collect::<Vec<_>>();
-        assert_eq!(matches, [(0, 0), (4, 4), (5, 5), (11, 11)]);
+        let matches = re.find_iter("test string").map(|m| m.range()).collect::<Vec<_>>();
+        assert_eq!(matches, [0..0, 4..4, 5..5, 11..11]);
     }
 
     #[test]
@@ -488,4 +585,27 @@ mod tests {
         assert_eq!(ms[0].pos(0).unwrap(), (1, 3));
         assert_eq!(ms[1].pos(0).unwrap(), (4, 5));
     }
+
+    #[test]
+    fn test_match_accessors() {
+        let re = Regex::new(r"\d+").unwrap();
+        let m = re.find_iter("a12b2").next().unwrap();
+        assert_eq!(m.start(), 1);
+        assert_eq!(m.end(), 3);
+        assert_eq!(m.range(), 1..3);
+        assert_eq!(m.as_str(), "12");
+    }
+
+    #[test]
+    fn test_captures_get() {
+        let re = Regex::new(r"e(l+)").unwrap();
+        let captures = re.captures("hello").unwrap();
+        let whole = captures.get(0).unwrap();
+        assert_eq!(whole.range(), 1..4);
+        assert_eq!(whole.as_str(), "ell");
+        let group = captures.get(1).unwrap();
+        assert_eq!(group.range(), 2..4);
+        assert_eq!(group.as_str(), "ll");
+        assert!(captures.get(2).is_none());
+    }
 }
@@ -0,0 +1,128 @@
+use libc::{c_int, c_void};
+use std::os::raw::c_char;
+use std::slice;
+use std::str;
+
+use super::{onig_sys, Regex};
+
+impl Regex {
+    /// Returns an iterator over the names of the capture groups in this
+    /// pattern, in order of group number. Group `0` (the whole match) is
+    /// never named and always yields `None`, as does any other group with
+    /// no `(?<name>...)` attached to it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate onig; use onig::Regex;
+    /// # fn main() {
+    /// let re = Regex::new(r"(?<year>\d{4})-(?<month>\d{2})").unwrap();
+    /// let names: Vec<_> = re.capture_names().collect();
+    /// assert_eq!(names, vec![None, Some("year"), Some("month")]);
+    /// # }
+    /// ```
+    pub fn capture_names<'r>(&'r self) -> CaptureNames<'r> {
+        let num_groups = unsafe { onig_sys::onig_number_of_captures(self.raw) };
+        let mut names: Vec<Option<&str>> = vec![None; num_groups as usize + 1];
+        unsafe {
+            onig_sys::onig_foreach_name(
+                self.raw,
+                foreach_name_callback,
+                &mut names as *mut Vec<Option<&str>> as *mut c_void,
+            );
+        }
+        CaptureNames { names: names.into_iter() }
+    }
+
+    /// Looks up every numbered group that `name` refers to. A name can be
+    /// attached to more than one group (most commonly across branches of an
+    /// alternation), so this returns all of them, in the order Oniguruma
+    /// reports them.
+    pub(crate) fn capture_group_numbers_for_name(&self, name: &str) -> Vec<c_int> {
+        let name_start = name.as_ptr() as *const c_char;
+        let name_end = unsafe { name_start.offset(name.len() as isize) };
+        let mut groups: *mut c_int = ::std::ptr::null_mut();
+        let num_groups = unsafe {
+            onig_sys::onig_name_to_group_numbers(self.raw, name_start, name_end, &mut groups)
+        };
+        if num_groups <= 0 {
+            return Vec::new();
+        }
+        unsafe { slice::from_raw_parts(groups, num_groups as usize).to_vec() }
+    }
+}
+
+/// An iterator over the names of a regular expression's capture groups, in
+/// order of group number. Created by
+/// [`Regex::capture_names`](struct.Regex.html#method.capture_names).
+pub struct CaptureNames<'r> {
+    names: ::std::vec::IntoIter<Option<&'r str>>,
+}
+
+impl<'r> Iterator for CaptureNames<'r> {
+    type Item = Option<&'r str>;
+
+    fn next(&mut self) -> Option<Option<&'r str>> {
+        self.names.next()
+    }
+}
+
+extern "C" fn foreach_name_callback(
+    name: *const c_char,
+    name_end: *const c_char,
+    num_groups: c_int,
+    group_numbers: *const c_int,
+    _regex: onig_sys::OnigRegex,
+    arg: *mut c_void,
+) -> c_int {
+    unsafe {
+        let names = &mut *(arg as *mut Vec<Option<&str>>);
+        let len = name_end as usize - name as usize;
+        let name = str::from_utf8_unchecked(slice::from_raw_parts(name as *const u8, len));
+        for &group in slice::from_raw_parts(group_numbers, num_groups as usize) {
+            if let Some(slot) = names.get_mut(group as usize) {
+                *slot = Some(name);
+            }
+        }
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn test_capture_names() {
+        let re = Regex::new(r"(?<year>\d{4})-(?<month>\d{2})").unwrap();
+        let names = re.capture_names().collect::<Vec<_>>();
+        assert_eq!(names, vec![None, Some("year"), Some("month")]);
+    }
+
+    #[test]
+    fn test_capture_names_no_names() {
+        let re = Regex::new(r"(\d+)-(\d+)").unwrap();
+        let names = re.capture_names().collect::<Vec<_>>();
+        assert_eq!(names, vec![None, None, None]);
+    }
+
+    #[test]
+    fn test_captures_name_and_name_pos() {
+        let re = Regex::new(r"(?<year>\d{4})-(?<month>\d{2})").unwrap();
+        let caps = re.captures("2014-05").unwrap();
+        assert_eq!(caps.name_pos("year"), Some((0, 4)));
+        assert_eq!(caps.name("year"), Some("2014"));
+        assert_eq!(caps.name("month"), Some("05"));
+        assert_eq!(caps.name("nope"), None);
+    }
+
+    #[test]
+    fn test_captures_name_picks_last_matching_group() {
+        // The name `num` is shared between two branches of an alternation,
+        // so only one of the two numbered groups it refers to matches any
+        // given time. `name`/`name_pos` should report the one that did.
+        let re = Regex::new(r"(?<num>[a-z]+)|\d+(?<num>[a-z]+)").unwrap();
+        let caps = re.captures("123abc").unwrap();
+        assert_eq!(caps.name("num"), Some("abc"));
+    }
+}
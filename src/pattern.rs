@@ -0,0 +1,113 @@
+#![cfg(feature = "pattern")]
+
+//! Implements the unstable `std::str::pattern::Pattern` trait for
+//! `&Regex`, so a compiled pattern can be passed directly to `str::find`,
+//! `str::split`, `str::matches`, and friends. This requires the `pattern`
+//! cargo feature, since `std::str::pattern` is itself unstable and only
+//! available on nightly Rust.
+
+use std::str::pattern::{Pattern, SearchStep, Searcher};
+
+use super::find::FindMatches;
+use super::Regex;
+
+impl<'r, 't> Pattern<'t> for &'r Regex {
+    type Searcher = RegexSearcher<'r, 't>;
+
+    fn into_searcher(self, haystack: &'t str) -> RegexSearcher<'r, 't> {
+        RegexSearcher {
+            haystack: haystack,
+            it: self.find_iter(haystack),
+            last_step_end: 0,
+            next_match: None,
+        }
+    }
+}
+
+/// The `Searcher` that backs `Pattern` for `&Regex`.
+///
+/// Drives `find_iter` over the remaining haystack, interleaving `Reject`
+/// steps for the unmatched gaps between matches with `Match` steps for
+/// each one. Zero-width matches are handled by `FindMatches` itself, which
+/// already advances by one `char` past an empty match to avoid looping
+/// forever.
+pub struct RegexSearcher<'r, 't> {
+    haystack: &'t str,
+    it: FindMatches<'r, 't>,
+    last_step_end: usize,
+    next_match: Option<(usize, usize)>,
+}
+
+unsafe impl<'r, 't> Searcher<'t> for RegexSearcher<'r, 't> {
+    #[inline]
+    fn haystack(&self) -> &'t str {
+        self.haystack
+    }
+
+    fn next(&mut self) -> SearchStep {
+        if let Some((s, e)) = self.next_match {
+            self.next_match = None;
+            self.last_step_end = e;
+            return SearchStep::Match(s, e);
+        }
+        match self.it.next() {
+            None => {
+                let len = self.haystack.len();
+                if self.last_step_end < len {
+                    let last = self.last_step_end;
+                    self.last_step_end = len;
+                    SearchStep::Reject(last, len)
+                } else {
+                    SearchStep::Done
+                }
+            }
+            Some(m) => {
+                let (s, e) = (m.start(), m.end());
+                if s > self.last_step_end {
+                    let last = self.last_step_end;
+                    self.next_match = Some((s, e));
+                    self.last_step_end = s;
+                    SearchStep::Reject(last, s)
+                } else {
+                    self.last_step_end = e;
+                    SearchStep::Match(s, e)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn test_pattern_split() {
+        let re = Regex::new(r"[0-9]+").unwrap();
+        let fields: Vec<&str> = "a1b22c333".split(&re).collect();
+        assert_eq!(fields, vec!["a", "b", "c", ""]);
+    }
+
+    #[test]
+    fn test_pattern_matches() {
+        let re = Regex::new(r"[0-9]+").unwrap();
+        let matches: Vec<&str> = "a1b22c333".matches(&re).collect();
+        assert_eq!(matches, vec!["1", "22", "333"]);
+    }
+
+    #[test]
+    fn test_pattern_find() {
+        let re = Regex::new(r"[0-9]+").unwrap();
+        assert_eq!("a1b22c333".find(&re), Some(1));
+    }
+
+    #[test]
+    fn test_pattern_zero_width_matches() {
+        // `\b` matches the empty string at every word boundary; the
+        // searcher must still interleave `Reject`/`Match` steps correctly
+        // instead of looping forever or skipping a boundary.
+        let re = Regex::new(r"\b").unwrap();
+        let fields: Vec<&str> = "ab cd".split(&re).collect();
+        assert_eq!(fields, vec!["", "ab", " ", "cd", ""]);
+    }
+}
@@ -0,0 +1,411 @@
+use std::ptr;
+use std::iter::Iterator;
+
+use libc::c_int;
+
+use super::{onig_sys, Region, Regex, SearchOptions, SEARCH_OPTION_NONE};
+use error::Error;
+
+/// The character encoding Oniguruma should use to interpret both a
+/// `RegexBytes` pattern and the haystacks it searches.
+///
+/// `Regex` (the `&str`-based API) is always compiled against `Utf8`. Pick
+/// one of the other variants when the bytes you're matching against --
+/// and the pattern describing them -- use a different encoding, such as
+/// raw binary data or a legacy Japanese encoding.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// Treat both the pattern and the haystack as 7-bit ASCII.
+    Ascii,
+    /// Treat both the pattern and the haystack as UTF-8. Equivalent to
+    /// what `Regex` uses internally.
+    Utf8,
+    /// Treat both the pattern and the haystack as EUC-JP.
+    EucJp,
+    /// Treat both the pattern and the haystack as Shift-JIS.
+    Sjis,
+    /// Treat both the pattern and the haystack as an opaque byte stream,
+    /// with no notion of multi-byte characters at all.
+    Binary,
+}
+
+impl Encoding {
+    fn as_onig_encoding(&self) -> onig_sys::OnigEncoding {
+        unsafe {
+            match *self {
+                Encoding::Ascii => &mut onig_sys::OnigEncodingASCII,
+                Encoding::Utf8 => &mut onig_sys::OnigEncodingUTF8,
+                Encoding::EucJp => &mut onig_sys::OnigEncodingEUC_JP,
+                Encoding::Sjis => &mut onig_sys::OnigEncodingSJIS,
+                Encoding::Binary => &mut onig_sys::OnigEncodingASCII,
+            }
+        }
+    }
+}
+
+impl Regex {
+    /// Compiles `pattern`, encoded as `encoding`, into a `Regex`. This is
+    /// what lets `RegexBytes::with_encoding` pick a non-UTF-8 encoding
+    /// instead of always going through the UTF-8-only `Regex::new`.
+    fn with_encoding(pattern: &[u8], encoding: Encoding) -> Result<Regex, Error> {
+        let mut raw = ptr::null_mut();
+        let mut error_info = unsafe { ::std::mem::zeroed() };
+        let start = pattern.as_ptr();
+        let result = unsafe {
+            onig_sys::onig_new(&mut raw,
+                               start,
+                               start.offset(pattern.len() as isize),
+                               SEARCH_OPTION_NONE.bits(),
+                               encoding.as_onig_encoding(),
+                               onig_sys::OnigDefaultSyntax,
+                               &mut error_info)
+        };
+        if result as c_int == onig_sys::ONIG_NORMAL {
+            Ok(Regex::from_raw(raw))
+        } else {
+            Err(Error::from_onig(result, &error_info))
+        }
+    }
+
+    /// Searches `text` for a match between byte offsets `from` and `to`,
+    /// exactly as `search_with_options` does for `&str`, except `text` is
+    /// treated as an opaque byte slice rather than validated UTF-8. This is
+    /// what lets `RegexBytes` reuse the same compiled pattern and region
+    /// machinery as `Regex`.
+    fn search_with_options_bytes(&self, text: &[u8], from: usize, to: usize,
+                                  options: SearchOptions, region: Option<&mut Region>)
+                                  -> Option<usize> {
+        let start = text.as_ptr();
+        let region = region.map_or(ptr::null_mut(), |r| r.raw_mut());
+        let result = unsafe {
+            onig_sys::onig_search(self.raw,
+                                  start,
+                                  start.offset(text.len() as isize),
+                                  start.offset(from as isize),
+                                  start.offset(to as isize),
+                                  region,
+                                  options.bits())
+        };
+        if result >= 0 {
+            Some(result as usize)
+        } else {
+            None
+        }
+    }
+}
+
+/// A compiled Oniguruma pattern that searches `&[u8]` haystacks instead of
+/// `&str`.
+///
+/// This is useful for patterns compiled against an encoding other than
+/// UTF-8 (raw bytes, EUC-JP, Shift-JIS, ...), where the haystack may
+/// contain byte sequences that aren't valid UTF-8 and slicing it as a
+/// `str` at arbitrary offsets would panic. `RegexBytes` mirrors the `Regex`
+/// API in `find.rs`, but every offset and substring it hands back refers
+/// to the underlying bytes rather than `char` boundaries, and the pattern
+/// is compiled against the `Encoding` passed to `with_encoding` rather
+/// than being fixed to UTF-8.
+pub struct RegexBytes {
+    regex: Regex,
+}
+
+impl RegexBytes {
+    /// Compiles a new byte-oriented pattern as UTF-8, with the default
+    /// options and syntax, as in `Regex::new`. Use `with_encoding` to pick
+    /// a different encoding for non-UTF-8 data.
+    pub fn new(pattern: &str) -> Result<RegexBytes, Error> {
+        RegexBytes::with_encoding(pattern.as_bytes(), Encoding::Utf8)
+    }
+
+    /// Compiles a new byte-oriented pattern, with `pattern` and any
+    /// haystack later searched both interpreted using `encoding`, rather
+    /// than being assumed to be UTF-8.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate onig; use onig::{Encoding, RegexBytes};
+    /// # fn main() {
+    /// let re = RegexBytes::with_encoding(b"[0-9]+", Encoding::Ascii).unwrap();
+    /// assert!(re.find_iter(b"a1b2").collect::<Vec<_>>().len() == 2);
+    /// # }
+    /// ```
+    pub fn with_encoding(pattern: &[u8], encoding: Encoding) -> Result<RegexBytes, Error> {
+        Regex::with_encoding(pattern, encoding).map(|regex| RegexBytes { regex: regex })
+    }
+
+    /// Returns the capture groups corresponding to the leftmost-first match
+    /// in `text`. If no match is found, then `None` is returned.
+    pub fn captures<'r, 't>(&'r self, text: &'t [u8]) -> Option<CapturesBytes<'r, 't>> {
+        let mut region = Region::new();
+        self.regex.search_with_options_bytes(text, 0, text.len(),
+                                             SEARCH_OPTION_NONE, Some(&mut region))
+            .map(|_| CapturesBytes {
+                regex: &self.regex,
+                text: text,
+                region: region,
+            })
+    }
+
+    /// Returns an iterator over each successive non-overlapping match in
+    /// `text`, yielding the start and end byte indices of each match.
+    pub fn find_iter<'r, 't>(&'r self, text: &'t [u8]) -> FindMatchesBytes<'r, 't> {
+        FindMatchesBytes {
+            regex: &self.regex,
+            region: Region::new(),
+            text: text,
+            last_end: 0,
+            skip_next_empty: false,
+        }
+    }
+
+    /// Returns an iterator over all the non-overlapping capture groups
+    /// matched in `text`.
+    pub fn captures_iter<'r, 't>(&'r self, text: &'t [u8]) -> FindCapturesBytes<'r, 't> {
+        FindCapturesBytes {
+            regex: &self.regex,
+            text: text,
+            last_end: 0,
+            skip_next_empty: false,
+        }
+    }
+
+    /// Returns an iterator of slices of `text` delimited by a match of the
+    /// regular expression, as `Regex::split` does for `&str`.
+    pub fn split<'r, 't>(&'r self, text: &'t [u8]) -> RegexSplitsBytes<'r, 't> {
+        RegexSplitsBytes {
+            finder: self.find_iter(text),
+            last: 0,
+        }
+    }
+}
+
+/// A group of captured byte slices for a single match against a
+/// `RegexBytes`.
+///
+/// `'r` is the lifetime of the `RegexBytes`'s underlying `Regex` and `'t`
+/// is the lifetime of the matched bytes.
+#[derive(Debug)]
+pub struct CapturesBytes<'r, 't> {
+    regex: &'r Regex,
+    text: &'t [u8],
+    region: Region,
+}
+
+impl<'r, 't> CapturesBytes<'r, 't> {
+    /// Returns the start and end positions of the Nth capture group.
+    pub fn pos(&self, pos: usize) -> Option<(usize, usize)> {
+        self.region.pos(pos)
+    }
+
+    /// Returns the matched bytes for the capture group `i`.
+    pub fn at(&self, pos: usize) -> Option<&'t [u8]> {
+        self.pos(pos).map(|(beg, end)| &self.text[beg..end])
+    }
+
+    /// Returns the start and end positions of the capture group named
+    /// `name`, as `Captures::name_pos` does for `&str` matches.
+    pub fn name_pos(&self, name: &str) -> Option<(usize, usize)> {
+        self.regex.capture_group_numbers_for_name(name)
+            .iter()
+            .filter_map(|&group| self.pos(group as usize))
+            .last()
+    }
+
+    /// Returns the matched bytes for the capture group named `name`.
+    pub fn name(&self, name: &str) -> Option<&'t [u8]> {
+        self.name_pos(name).map(|(beg, end)| &self.text[beg..end])
+    }
+
+    /// Returns the number of captured groups.
+    pub fn len(&self) -> usize {
+        self.region.len()
+    }
+
+    /// Returns true if and only if there are no captured groups.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// An iterator over all non-overlapping matches of a `RegexBytes` in a byte
+/// slice.
+///
+/// `'r` is the lifetime of the underlying `Regex` and `'t` is the lifetime
+/// of the matched bytes.
+pub struct FindMatchesBytes<'r, 't> {
+    regex: &'r Regex,
+    region: Region,
+    text: &'t [u8],
+    last_end: usize,
+    skip_next_empty: bool,
+}
+
+impl<'r, 't> Iterator for FindMatchesBytes<'r, 't> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        if self.last_end > self.text.len() {
+            return None;
+        }
+        self.region.clear();
+        let r = self.regex.search_with_options_bytes(self.text,
+                                                      self.last_end,
+                                                      self.text.len(),
+                                                      SEARCH_OPTION_NONE,
+                                                      Some(&mut self.region));
+        if r.is_none() {
+            return None;
+        }
+        let (s, e) = self.region.pos(0).unwrap();
+        self.last_end = e;
+
+        // Don't accept empty matches immediately following a match.
+        // i.e., no infinite loops please.
+        if e == s {
+            self.last_end += 1;
+            if self.skip_next_empty {
+                self.skip_next_empty = false;
+                return self.next();
+            }
+        } else {
+            self.skip_next_empty = true;
+        }
+
+        Some((s, e))
+    }
+}
+
+/// An iterator that yields all non-overlapping capture groups matching a
+/// `RegexBytes` in a byte slice.
+///
+/// `'r` is the lifetime of the underlying `Regex` and `'t` is the lifetime
+/// of the matched bytes.
+pub struct FindCapturesBytes<'r, 't> {
+    regex: &'r Regex,
+    text: &'t [u8],
+    last_end: usize,
+    skip_next_empty: bool,
+}
+
+impl<'r, 't> Iterator for FindCapturesBytes<'r, 't> {
+    type Item = CapturesBytes<'r, 't>;
+
+    fn next(&mut self) -> Option<CapturesBytes<'r, 't>> {
+        if self.last_end > self.text.len() {
+            return None;
+        }
+
+        let mut region = Region::new();
+        let r = self.regex.search_with_options_bytes(self.text,
+                                                      self.last_end,
+                                                      self.text.len(),
+                                                      SEARCH_OPTION_NONE,
+                                                      Some(&mut region));
+        if r.is_none() {
+            return None;
+        }
+        let (s, e) = region.pos(0).unwrap();
+        self.last_end = e;
+
+        if e == s {
+            self.last_end += 1;
+            if self.skip_next_empty {
+                self.skip_next_empty = false;
+                return self.next();
+            }
+        } else {
+            self.skip_next_empty = true;
+        }
+        Some(CapturesBytes {
+            regex: self.regex,
+            text: self.text,
+            region: region,
+        })
+    }
+}
+
+/// Yields all byte-slices delimited by a `RegexBytes` match.
+///
+/// `'r` is the lifetime of the underlying `Regex` and `'t` is the lifetime
+/// of the slice being split.
+pub struct RegexSplitsBytes<'r, 't> {
+    finder: FindMatchesBytes<'r, 't>,
+    last: usize,
+}
+
+impl<'r, 't> Iterator for RegexSplitsBytes<'r, 't> {
+    type Item = &'t [u8];
+
+    fn next(&mut self) -> Option<&'t [u8]> {
+        let text = self.finder.text;
+        match self.finder.next() {
+            None => {
+                if self.last >= text.len() {
+                    None
+                } else {
+                    let s = &text[self.last..];
+                    self.last = text.len();
+                    Some(s)
+                }
+            }
+            Some((s, e)) => {
+                let matched = &text[self.last..s];
+                self.last = e;
+                Some(matched)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "\xffze" is not valid UTF-8 (0xff is never a valid UTF-8 lead byte),
+    // so slicing it as a `str` at these offsets would panic -- exactly the
+    // case `RegexBytes` exists to handle.
+    const NON_UTF8: &'static [u8] = b"\xffze\xffze";
+
+    #[test]
+    fn test_find_iter_non_utf8() {
+        let re = RegexBytes::with_encoding(b"ze", Encoding::Binary).unwrap();
+        let ms = re.find_iter(NON_UTF8).collect::<Vec<_>>();
+        assert_eq!(ms, vec![(1, 3), (4, 6)]);
+    }
+
+    #[test]
+    fn test_captures_iter_non_utf8() {
+        let re = RegexBytes::with_encoding(b"(z)(e)", Encoding::Binary).unwrap();
+        let caps = re.captures_iter(NON_UTF8).collect::<Vec<_>>();
+        assert_eq!(caps.len(), 2);
+        assert_eq!(caps[0].at(1), Some(&b"z"[..]));
+        assert_eq!(caps[0].at(2), Some(&b"e"[..]));
+    }
+
+    #[test]
+    fn test_split_non_utf8() {
+        let re = RegexBytes::with_encoding(b"ze", Encoding::Binary).unwrap();
+        let fields = re.split(NON_UTF8).collect::<Vec<_>>();
+        assert_eq!(fields, vec![&b"\xff"[..], &b"\xff"[..], &b""[..]]);
+    }
+
+    #[test]
+    fn test_with_encoding_ascii() {
+        let re = RegexBytes::with_encoding(b"[0-9]+", Encoding::Ascii).unwrap();
+        let ms = re.find_iter(b"a1b22c333").collect::<Vec<_>>();
+        assert_eq!(ms, vec![(1, 2), (3, 5), (6, 9)]);
+    }
+
+    #[test]
+    fn test_captures_iter_zero_width_matches_one_per_boundary() {
+        // `\b` matches the empty string at every word boundary in "ab cd":
+        // byte offsets 0, 2, 3 and 5. `captures_iter` must report each one
+        // exactly once, the same as `find_iter` does for this pattern.
+        let re = RegexBytes::with_encoding(b"\\b", Encoding::Binary).unwrap();
+        let positions = re.captures_iter(b"ab cd")
+                           .map(|caps| caps.pos(0).unwrap())
+                           .collect::<Vec<_>>();
+        assert_eq!(positions, vec![(0, 0), (2, 2), (3, 3), (5, 5)]);
+    }
+}
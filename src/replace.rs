@@ -0,0 +1,267 @@
+use std::borrow::Cow;
+
+use super::{Captures, Regex};
+
+impl Regex {
+    /// Replaces the leftmost-first match in `text` with the replacement
+    /// provided. The replacement can be a `&str` or a closure that takes
+    /// a `&Captures` and returns a replacement string.
+    ///
+    /// If no match is found, then `text` is returned unchanged, borrowed
+    /// rather than copied.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate onig; use onig::Regex;
+    /// # fn main() {
+    /// let re = Regex::new(r"[a-z]+").unwrap();
+    /// assert_eq!(re.replace("123abc456", "X"), "123X456");
+    /// # }
+    /// ```
+    pub fn replace<'t, R: Replacer>(&self, text: &'t str, rep: R) -> Cow<'t, str> {
+        self.replacen(text, 1, rep)
+    }
+
+    /// Replaces all non-overlapping matches in `text` with the replacement
+    /// provided. This is the same as calling `replacen` with `limit` set
+    /// to `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate onig; use onig::Regex;
+    /// # fn main() {
+    /// let re = Regex::new(r"[0-9]+").unwrap();
+    /// assert_eq!(re.replace_all("a1b22c333", "#"), "a#b#c#");
+    /// # }
+    /// ```
+    pub fn replace_all<'t, R: Replacer>(&self, text: &'t str, rep: R) -> Cow<'t, str> {
+        self.replacen(text, 0, rep)
+    }
+
+    /// Replaces at most `limit` non-overlapping matches in `text` with the
+    /// replacement provided. If `limit` is `0`, then every match is
+    /// replaced.
+    ///
+    /// The replacement can be a `&str`, in which case `$0`, `$1`, ... refer
+    /// to numbered capture groups and `$name`/`${name}` refer to named
+    /// capture groups (`$$` is a literal `$`), or a closure `FnMut(&Captures)
+    /// -> String` for when the replacement needs to compute something from
+    /// the match.
+    ///
+    /// When there is no match, `text` is returned unchanged without any
+    /// allocation, via `Cow::Borrowed`.
+    pub fn replacen<'t, R: Replacer>(&self, text: &'t str, limit: usize, mut rep: R)
+                                     -> Cow<'t, str> {
+        let mut it = self.captures_iter(text).enumerate().peekable();
+        if it.peek().is_none() {
+            return Cow::Borrowed(text);
+        }
+        let mut new = String::with_capacity(text.len());
+        let mut last_end = 0;
+        for (i, caps) in it {
+            if limit > 0 && i >= limit {
+                break;
+            }
+            let (s, e) = caps.pos(0).unwrap();
+            new.push_str(&text[last_end..s]);
+            rep.reg_replace(&caps, &mut new);
+            last_end = e;
+        }
+        new.push_str(&text[last_end..]);
+        Cow::Owned(new)
+    }
+}
+
+/// A trait for types that can be used to replace matches in a string.
+///
+/// This is implemented for `&str`, which expands `$`-style references to
+/// capture groups as described on
+/// [`Regex::replacen`](struct.Regex.html#method.replacen), and for any
+/// `FnMut(&Captures) -> String`, which is called once per match.
+pub trait Replacer {
+    /// Appends the replacement text for `caps` onto `dst`.
+    fn reg_replace(&mut self, caps: &Captures, dst: &mut String);
+}
+
+impl<'a> Replacer for &'a str {
+    fn reg_replace(&mut self, caps: &Captures, dst: &mut String) {
+        expand(caps, *self, dst);
+    }
+}
+
+impl<F> Replacer for F
+    where F: FnMut(&Captures) -> String
+{
+    fn reg_replace(&mut self, caps: &Captures, dst: &mut String) {
+        dst.push_str(&(*self)(caps));
+    }
+}
+
+/// Expands `template` against `caps`, appending the result onto `dst`.
+///
+/// `$0`, `$1`, ... are replaced with the text of the corresponding numbered
+/// capture group, and `${name}` or `$name` (the name ends at the first
+/// non-word character) with the named capture group. `$$` is a literal
+/// `$`. Groups that don't exist, or that didn't participate in the match,
+/// expand to the empty string.
+fn expand(caps: &Captures, mut template: &str, dst: &mut String) {
+    while !template.is_empty() {
+        match template.find('$') {
+            None => break,
+            Some(i) => {
+                dst.push_str(&template[..i]);
+                template = &template[i..];
+            }
+        }
+        if template.as_bytes().get(1) == Some(&b'$') {
+            dst.push('$');
+            template = &template[2..];
+            continue;
+        }
+        debug_assert!(!template.is_empty());
+        let cap_ref = match find_cap_ref(template) {
+            Some(cap_ref) => cap_ref,
+            None => {
+                dst.push('$');
+                template = &template[1..];
+                continue;
+            }
+        };
+        template = &template[cap_ref.end..];
+        match cap_ref.group {
+            CapRefGroup::Number(i) => dst.push_str(caps.at(i).unwrap_or("")),
+            CapRefGroup::Name(name) => dst.push_str(caps.name(name).unwrap_or("")),
+        }
+    }
+    dst.push_str(template);
+}
+
+struct CapRef<'t> {
+    group: CapRefGroup<'t>,
+    end: usize,
+}
+
+enum CapRefGroup<'t> {
+    Number(usize),
+    Name(&'t str),
+}
+
+/// Parses a capture group reference (`$0`, `$123`, `$name` or `${name}`)
+/// from the start of `text`, which is expected to start with `$`. Returns
+/// the parsed reference along with the length of `text` it consumed, or
+/// `None` if `text` doesn't begin with a valid reference.
+fn find_cap_ref(text: &str) -> Option<CapRef> {
+    let mut chars = text.char_indices();
+    debug_assert_eq!(chars.next().map(|(_, c)| c), Some('$'));
+
+    let braced = text[1..].starts_with('{');
+    let rest = if braced { &text[2..] } else { &text[1..] };
+    let name_end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                        .unwrap_or_else(|| rest.len());
+    if name_end == 0 {
+        return None;
+    }
+    let name = &rest[..name_end];
+    if braced {
+        if !rest[name_end..].starts_with('}') {
+            return None;
+        }
+        let end = 1 + 1 + name_end + 1;
+        return Some(CapRef { group: to_group(name), end: end });
+    }
+    Some(CapRef { group: to_group(name), end: 1 + name_end })
+}
+
+fn to_group(name: &str) -> CapRefGroup {
+    match name.parse::<usize>() {
+        Ok(i) => CapRefGroup::Number(i),
+        Err(_) => CapRefGroup::Name(name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use super::super::*;
+
+    #[test]
+    fn test_replace_first_only() {
+        let re = Regex::new(r"[0-9]+").unwrap();
+        assert_eq!(re.replace("a1b22c333", "#"), "a#b22c333");
+    }
+
+    #[test]
+    fn test_replace_all() {
+        let re = Regex::new(r"[0-9]+").unwrap();
+        assert_eq!(re.replace_all("a1b22c333", "#"), "a#b#c#");
+    }
+
+    #[test]
+    fn test_replacen_limit() {
+        let re = Regex::new(r"[0-9]+").unwrap();
+        assert_eq!(re.replacen("a1b22c333", 2, "#"), "a#b#c333");
+    }
+
+    #[test]
+    fn test_replacen_zero_limit_means_all() {
+        let re = Regex::new(r"[0-9]+").unwrap();
+        assert_eq!(re.replacen("a1b22c333", 0, "#"), "a#b#c#");
+    }
+
+    #[test]
+    fn test_replace_no_match_borrows_input() {
+        let re = Regex::new(r"[0-9]+").unwrap();
+        match re.replace_all("abc", "#") {
+            Cow::Borrowed(s) => assert_eq!(s, "abc"),
+            Cow::Owned(_) => panic!("expected a borrowed Cow when there's no match"),
+        }
+    }
+
+    #[test]
+    fn test_expand_numbered_groups() {
+        let re = Regex::new(r"(\d{4})-(\d{2})").unwrap();
+        assert_eq!(re.replace("2014-05", "$2/$1"), "05/2014");
+    }
+
+    #[test]
+    fn test_expand_named_groups() {
+        let re = Regex::new(r"(?<year>\d{4})-(?<month>\d{2})").unwrap();
+        assert_eq!(re.replace("2014-05", "${month}/${year}"), "05/2014");
+        assert_eq!(re.replace("2014-05", "$month/$year"), "05/2014");
+    }
+
+    #[test]
+    fn test_expand_bare_name_stops_at_non_word_char() {
+        let re = Regex::new(r"(?<year>\d{4})").unwrap();
+        assert_eq!(re.replace("2014", "$year!"), "2014!");
+    }
+
+    #[test]
+    fn test_expand_literal_dollar() {
+        let re = Regex::new(r"\d+").unwrap();
+        assert_eq!(re.replace("42", "$$$0"), "$42");
+    }
+
+    #[test]
+    fn test_expand_unknown_group_is_empty() {
+        let re = Regex::new(r"(\d+)").unwrap();
+        assert_eq!(re.replace("42", "[$1][$5][${nope}]"), "[42][][]");
+    }
+
+    #[test]
+    fn test_expand_non_participating_group_is_empty() {
+        let re = Regex::new(r"(a)|(b)").unwrap();
+        assert_eq!(re.replace("b", "$1-$2"), "-b");
+    }
+
+    #[test]
+    fn test_replace_with_closure() {
+        let re = Regex::new(r"[a-z]+").unwrap();
+        let result = re.replace_all("abc def", |caps: &Captures| {
+            caps.at(0).unwrap().to_uppercase()
+        });
+        assert_eq!(result, "ABC DEF");
+    }
+}